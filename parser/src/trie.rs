@@ -31,6 +31,15 @@ fn key_len(k: &TrieKey) -> usize {
     k.len() * 2
 }
 
+/// Inverse of `cast_slice::<TrieKeyElement, u8>`; reassembles the `[u8]` key bytes handed back by
+/// the underlying map into a `TrieKey`.
+fn key_from_bytes(bytes: &[u8]) -> TrieKey {
+    bytes
+        .chunks_exact(2)
+        .map(|b| TrieKeyElement::from_ne_bytes([b[0], b[1]]))
+        .collect()
+}
+
 impl<T> Trie<T> {
     pub fn new() -> Self {
         Self {
@@ -53,16 +62,34 @@ impl<T> Trie<T> {
         self.inner.insert(cast_slice(&key), val);
     }
 
+    pub fn remove(&mut self, key: &TrieKey) -> Option<T> {
+        self.inner.remove(cast_slice(key))
+    }
+
+    pub fn contains_key(&self, key: &TrieKey) -> bool {
+        self.inner.contains_key(cast_slice(key))
+    }
+
+    pub fn get_mut(&mut self, key: &TrieKey) -> Option<&mut T> {
+        self.inner.get_mut(cast_slice(key))
+    }
+
+    /// Iterates over all entries in the trie. Intended for incrementally diffing against a new
+    /// set of entries on live-reload rather than rebuilding the whole trie from scratch.
+    pub fn iter(&self) -> impl Iterator<Item = (TrieKey, &T)> {
+        self.inner.iter().map(|(k, v)| (key_from_bytes(&k), v))
+    }
+
     pub fn get_or_descendant_exists(&self, key: &TrieKey) -> GetOrDescendentExistsResult<T>
     where
-        T: Copy,
+        T: Clone,
     {
         let mut descendants = self.inner.iter_prefix(cast_slice(key));
         match descendants.next() {
             None => NotInTrie,
             Some(descendant) => {
                 if descendant.0.len() == key_len(key) {
-                    HasValue(*descendant.1)
+                    HasValue(descendant.1.clone())
                 } else {
                     InTrie
                 }
@@ -70,3 +97,42 @@ impl<T> Trie<T> {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn iter_round_trips_multi_element_keys() {
+        let mut trie = Trie::new();
+        let key: TrieKey = vec![1, 2, 3];
+        trie.insert(key.clone(), "abc");
+
+        let entries: Vec<_> = trie.iter().collect();
+
+        assert_eq!(entries, vec![(key, &"abc")]);
+    }
+
+    #[test]
+    fn remove_returns_value_and_contains_key_flips() {
+        let mut trie = Trie::new();
+        let key: TrieKey = vec![1, 2];
+        trie.insert(key.clone(), "ab");
+
+        assert!(trie.contains_key(&key));
+        assert_eq!(trie.remove(&key), Some("ab"));
+        assert!(!trie.contains_key(&key));
+        assert_eq!(trie.remove(&key), None);
+    }
+
+    #[test]
+    fn get_mut_allows_updating_value_in_place() {
+        let mut trie = Trie::new();
+        let key: TrieKey = vec![1];
+        trie.insert(key.clone(), 1u32);
+
+        *trie.get_mut(&key).unwrap() += 1;
+
+        assert_eq!(trie.get_or_descendant_exists(&key), HasValue(2));
+    }
+}
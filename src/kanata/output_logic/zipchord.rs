@@ -1,6 +1,7 @@
 use super::*;
 
-use kanata_parser::trie::Trie;
+use kanata_parser::trie::GetOrDescendentExistsResult::*;
+use kanata_parser::trie::{Trie, TrieKey};
 use rustc_hash::FxHashSet;
 
 use std::sync::Arc;
@@ -97,21 +98,42 @@ struct ZchDynamicState {
     zchd_ticks_until_enabled: u16,
     /// Tracks the actually pressed keys to know when state can be reset.
     zchd_pressed_keys: FxHashSet<OsCode>,
+    /// Number of characters currently on-screen for the in-progress chord attempt, i.e. how many
+    /// backspaces are required to erase everything typed so far before an activation can type its
+    /// replacement in its place. This is a count of chars, not bytes, so that multi-byte chord
+    /// output is erased correctly.
+    zchd_num_backspaces: u16,
+    /// Ticks elapsed since the last keypress was buffered into `zchd_sorted_inputs`. Used to flush
+    /// a partial chord as literal keystrokes if the user is typing too slowly for a longer chord
+    /// to realistically still be their intent.
+    zchd_ticks_since_last_press: u16,
 }
 
 impl ZchDynamicState {
     fn zchd_is_disabled(&self) -> bool {
         self.zchd_enabled_state == ZchEnabledState::ZchDisabled
     }
-    fn zchd_tick(&mut self) {
+    fn zchd_tick(&mut self, cfg: &ZchConfig) {
         const TICKS_UNTIL_FORCE_STATE_RESET: u16 = 10000;
         self.zchd_ticks_since_state_change += 1;
+        self.zchd_ticks_since_last_press = self.zchd_ticks_since_last_press.saturating_add(1);
         if self.zchd_enabled_state == ZchEnabledState::ZchWaitEnable {
             self.zchd_ticks_until_enabled = self.zchd_ticks_until_enabled.saturating_sub(1);
             if self.zchd_ticks_until_enabled == 0 {
                 self.zchd_enabled_state = ZchEnabledState::ZchEnabled;
             }
         }
+        let is_buffering_partial_chord = (!self.zchd_sorted_inputs.zch_inputs.zch_keys.is_empty()
+            || self.zchd_prioritized_chords.is_some())
+            && self.zchd_enabled_state != ZchEnabledState::ZchDisabled;
+        if is_buffering_partial_chord
+            && self.zchd_ticks_since_last_press >= cfg.zch_cfg_ticks_chord_timeout
+        {
+            log::debug!("zch chord timeout; flushing buffered input as literal keys");
+            self.zchd_sorted_inputs = ZchSortedInputs::default();
+            self.zchd_prioritized_chords = None;
+            self.zchd_num_backspaces = 0;
+        }
         if self.zchd_ticks_since_state_change > TICKS_UNTIL_FORCE_STATE_RESET {
             self.zchd_reset();
         }
@@ -128,6 +150,8 @@ impl ZchDynamicState {
         self.zchd_pressed_keys.clear();
         self.zchd_sorted_inputs.zch_inputs.zch_keys.clear();
         self.zchd_prioritized_chords = None;
+        self.zchd_num_backspaces = 0;
+        self.zchd_ticks_since_last_press = 0;
     }
     /// Returns true if dynamic zch state is such that idling optimization can activate.
     fn zchd_is_idle(&self) -> bool {
@@ -139,12 +163,63 @@ impl ZchDynamicState {
     fn zchd_press_key(&mut self, osc: OsCode) {
         self.zchd_pressed_keys.insert(osc);
         self.zchd_sorted_inputs.zchsi_insert(osc);
+        self.zchd_num_backspaces = self.zchd_num_backspaces.saturating_add(1);
+        self.zchd_ticks_since_last_press = 0;
+    }
+    /// Looks `key` up against the prioritized (followup) chords first if any are active. If the
+    /// prioritized chords don't match, they're abandoned and the full chord set is consulted
+    /// instead, so the rest of the dictionary stays reachable mid-sequence (e.g. right after `dy`
+    /// activates `day` with `1`/`2` followups, typing the first letter of an unrelated chord like
+    /// `apple` must still be able to match against the full dictionary).
+    fn zchd_resolve_chord(
+        &mut self,
+        all_chords: &ZchPossibleChords,
+        key: &TrieKey,
+    ) -> GetOrDescendentExistsResult<ZchChordOutput> {
+        let result = match &self.zchd_prioritized_chords {
+            Some(chords) => chords.0.get_or_descendant_exists(key),
+            None => all_chords.0.get_or_descendant_exists(key),
+        };
+        if matches!(result, NotInTrie) && self.zchd_prioritized_chords.is_some() {
+            self.zchd_prioritized_chords = None;
+            // The previous activation's output is no longer part of this attempt; only the keys
+            // buffered since that activation remain to be erased.
+            self.zchd_num_backspaces = key.len() as u16;
+            all_chords.0.get_or_descendant_exists(key)
+        } else {
+            result
+        }
+    }
+    /// Updates state following a chord activation: clears the raw input buffer now that it's been
+    /// replaced by the chord's output, and arms `zchd_prioritized_chords`/`zchd_num_backspaces` for
+    /// the chord's followups, if any, so a subsequent activation knows what's on-screen to erase.
+    ///
+    /// `chord.zch_output.chars().count()` is used as the on-screen glyph count, which assumes one
+    /// glyph is rendered per `char` typed. This is a known limitation: it undercounts for output
+    /// containing combining marks (multiple chars render as one glyph) and, on platforms that type
+    /// via UTF-16 surrogate pairs, could miscount astral-plane scalars too. Plain ASCII/BMP output,
+    /// which is what chord dictionaries are expected to contain in practice, is unaffected.
+    fn zchd_activate(&mut self, chord: &ZchChordOutput) {
+        self.zchd_sorted_inputs = ZchSortedInputs::default();
+        self.zchd_num_backspaces = match &chord.zch_followups {
+            Some(_) => chord.zch_output.chars().count() as u16,
+            None => 0,
+        };
+        self.zchd_prioritized_chords = chord.zch_followups.clone();
     }
     fn zchd_release_key(&mut self, osc: OsCode) {
         self.zchd_pressed_keys.remove(&osc);
         self.zchd_enabled_state = match self.zchd_pressed_keys.is_empty() {
             true => ZchEnabledState::ZchWaitEnable,
-            false => ZchEnabledState::ZchDisabled,
+            false => {
+                // Other keys are still held with no chord having activated, so this attempt is
+                // abandoned. Clear the buffered input now rather than leaving it to leak into the
+                // next attempt once zch re-enables.
+                self.zchd_sorted_inputs = ZchSortedInputs::default();
+                self.zchd_prioritized_chords = None;
+                self.zchd_num_backspaces = 0;
+                ZchEnabledState::ZchDisabled
+            }
         };
     }
 }
@@ -173,14 +248,36 @@ impl ZchState {
         }
         self.zchd.zchd_state_change(&self.zch_cfg);
         self.zchd.zchd_press_key(osc);
-        // check prioritized chords
-        // check regular chords
-        // if neither has any potential activation left, disable
-        if todo!() {
-            self.zchd.zchd_enabled_state = ZchEnabledState::ZchDisabled;
-            return kb.press_key(osc);
+
+        let key = self.zchd.zchd_sorted_inputs.zch_inputs.zch_keys.clone();
+        let result = self.zchd.zchd_resolve_chord(&self.zch_chords, &key);
+
+        match result {
+            // Neither the prioritized nor the regular chords have any potential activation left;
+            // give up on this input and disable until the next idle reset.
+            NotInTrie => {
+                self.zchd.zchd_enabled_state = ZchEnabledState::ZchDisabled;
+                self.zchd.zchd_sorted_inputs = ZchSortedInputs::default();
+                self.zchd.zchd_prioritized_chords = None;
+                self.zchd.zchd_num_backspaces = 0;
+                kb.press_key(osc)
+            }
+            // A longer chord is still reachable; keep buffering and pass the raw key through.
+            InTrie => kb.press_key(osc),
+            // Exact match: the raw keys typed so far for this chord must be erased and replaced
+            // with the chord's output.
+            HasValue(chord) => {
+                // Forward the completing key like every other buffered key before it, then erase
+                // the whole buffered sequence (including it, hence the pre-incremented backspace
+                // count) and replace it with the chord's output. This briefly flashes the final
+                // key before erasing it, same as every earlier key in the buffer already did.
+                kb.press_key(osc)?;
+                zch_backspace(kb, self.zchd.zchd_num_backspaces)?;
+                zch_output_text(kb, &chord.zch_output)?;
+                self.zchd.zchd_activate(&chord);
+                Ok(())
+            }
         }
-        todo!()
     }
     /// Zch handling for key releases.
     pub(crate) fn zch_release_key(
@@ -197,7 +294,7 @@ impl ZchState {
     }
     /// Tick the zch output state.
     pub(crate) fn zch_tick(&mut self) {
-        self.zchd.zchd_tick();
+        self.zchd.zchd_tick(&self.zch_cfg);
     }
     /// Returns true if zch state has no further processing so the idling optimization can
     /// activate.
@@ -206,6 +303,27 @@ impl ZchState {
     }
 }
 
+/// Sends `ndel` backspace taps to erase characters typed during chord buffering.
+fn zch_backspace(kb: &mut KbdOut, ndel: u16) -> Result<(), std::io::Error> {
+    for _ in 0..ndel {
+        kb.press_key(OsCode::KEY_BACKSPACE)?;
+        kb.release_key(OsCode::KEY_BACKSPACE)?;
+    }
+    Ok(())
+}
+
+/// Types `s` out via `KbdOut`'s unicode text-entry path, the same mechanism used elsewhere in
+/// kanata for arbitrary text output. This avoids reconstructing keystrokes from characters, which
+/// would both be lossy (dictionaries commonly contain apostrophes, hyphens, and other punctuation
+/// outside a hand-picked set) and layout-dependent (a literal `OsCode` only yields the intended
+/// character under a US-QWERTY OS keyboard layout).
+fn zch_output_text(kb: &mut KbdOut, s: &str) -> Result<(), std::io::Error> {
+    for c in s.chars() {
+        kb.send_unicode(c)?;
+    }
+    Ok(())
+}
+
 static ZCH: Lazy<Mutex<ZchState>> = Lazy::new(|| Mutex::new(Default::default()));
 
 pub(crate) fn zch() -> MutexGuard<'static, ZchState> {
@@ -222,11 +340,157 @@ pub(crate) fn zch() -> MutexGuard<'static, ZchState> {
 #[derive(Debug)]
 struct ZchConfig {
     zch_cfg_ticks_wait_enable: u16,
+    /// How many ticks of inactivity are allowed while a partial chord is buffered before it is
+    /// flushed as literal keystrokes. Prevents slow deliberate typing from being silently
+    /// swallowed while a longer chord is still theoretically reachable.
+    zch_cfg_ticks_chord_timeout: u16,
 }
 impl Default for ZchConfig {
     fn default() -> Self {
         Self {
             zch_cfg_ticks_wait_enable: 50,
+            zch_cfg_ticks_chord_timeout: 1000,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn key_for(oscs: &[OsCode]) -> TrieKey {
+        let mut chord = ZchSortedChord::default();
+        for osc in oscs {
+            chord.zch_insert((*osc).into());
         }
+        chord.zch_keys
+    }
+
+    fn trie_from(entries: Vec<(TrieKey, ZchChordOutput)>) -> ZchPossibleChords {
+        let mut trie = Trie::new();
+        for (key, val) in entries {
+            trie.insert(key, val);
+        }
+        ZchPossibleChords(trie)
+    }
+
+    fn output(s: &str, followups: Option<Arc<ZchPossibleChords>>) -> ZchChordOutput {
+        ZchChordOutput {
+            zch_output: s.into(),
+            zch_followups: followups,
+        }
+    }
+
+    #[test]
+    fn resolve_chord_falls_back_to_full_dictionary_once_followups_stop_matching() {
+        let all_chords = trie_from(vec![(key_for(&[OsCode::KEY_A]), output("apple", None))]);
+        let followups = Arc::new(trie_from(vec![(
+            key_for(&[OsCode::KEY_1]),
+            output("Monday", None),
+        )]));
+
+        let mut zchd = ZchDynamicState::default();
+        zchd.zchd_prioritized_chords = Some(followups);
+        zchd.zchd_press_key(OsCode::KEY_A);
+        let key = zchd.zchd_sorted_inputs.zch_inputs.zch_keys.clone();
+
+        let result = zchd.zchd_resolve_chord(&all_chords, &key);
+
+        match result {
+            HasValue(chord) => assert_eq!(chord.zch_output.as_ref(), "apple"),
+            other => panic!("expected apple to activate via fallback, got {other:?}"),
+        }
+        assert!(
+            zchd.zchd_prioritized_chords.is_none(),
+            "stale followups must be abandoned once the fallback is used"
+        );
+    }
+
+    #[test]
+    fn release_mid_sequential_typing_preserves_buffered_input() {
+        let mut zchd = ZchDynamicState::default();
+        zchd.zchd_press_key(OsCode::KEY_D);
+        zchd.zchd_release_key(OsCode::KEY_D);
+
+        // Sequential typing (press then release one key at a time) must not be treated as an
+        // abandoned chord attempt; the buffered input is still needed for the next key.
+        assert_eq!(zchd.zchd_enabled_state, ZchEnabledState::ZchWaitEnable);
+        assert_eq!(zchd.zchd_sorted_inputs.zch_inputs.zch_keys.len(), 1);
+        assert_eq!(zchd.zchd_num_backspaces, 1);
+    }
+
+    #[test]
+    fn release_while_another_key_still_held_clears_buffered_input() {
+        let mut zchd = ZchDynamicState::default();
+        zchd.zchd_press_key(OsCode::KEY_D);
+        zchd.zchd_press_key(OsCode::KEY_Y);
+        zchd.zchd_release_key(OsCode::KEY_D); // KEY_Y is still held: this disables mid-buffer.
+
+        assert_eq!(zchd.zchd_enabled_state, ZchEnabledState::ZchDisabled);
+        assert!(zchd.zchd_sorted_inputs.zch_inputs.zch_keys.is_empty());
+        assert_eq!(zchd.zchd_num_backspaces, 0);
+    }
+
+    #[test]
+    fn activate_arms_followups_and_then_clears_backspaces_once_exhausted() {
+        // dy -> day, dy 1 -> Monday, mirroring the doc-comment example on `ZchChordOutput`.
+        let monday_followups = Arc::new(trie_from(vec![(
+            key_for(&[OsCode::KEY_1]),
+            output("Monday", None),
+        )]));
+        let day = output("day", Some(monday_followups));
+
+        let mut zchd = ZchDynamicState::default();
+        zchd.zchd_press_key(OsCode::KEY_D);
+        zchd.zchd_press_key(OsCode::KEY_Y);
+        assert_eq!(zchd.zchd_num_backspaces, 2, "\"dy\" buffered so far");
+
+        zchd.zchd_activate(&day);
+        assert_eq!(
+            zchd.zchd_num_backspaces, 3,
+            "\"day\" is now on-screen and must be fully erasable by the next activation"
+        );
+        assert!(zchd.zchd_prioritized_chords.is_some());
+
+        zchd.zchd_press_key(OsCode::KEY_1);
+        assert_eq!(
+            zchd.zchd_num_backspaces, 4,
+            "the followup key is buffered on top of \"day\" already on-screen"
+        );
+
+        zchd.zchd_activate(&output("Monday", None));
+        assert_eq!(
+            zchd.zchd_num_backspaces, 0,
+            "no further followups, so there's nothing left to erase"
+        );
+        assert!(zchd.zchd_prioritized_chords.is_none());
+    }
+
+    #[test]
+    fn tick_flushes_buffered_chord_after_timeout_elapses() {
+        let cfg = ZchConfig {
+            zch_cfg_ticks_chord_timeout: 3,
+            ..Default::default()
+        };
+        let mut zchd = ZchDynamicState::default();
+        zchd.zchd_press_key(OsCode::KEY_D);
+
+        for _ in 0..2 {
+            zchd.zchd_tick(&cfg);
+        }
+        assert_eq!(
+            zchd.zchd_sorted_inputs.zch_inputs.zch_keys.len(),
+            1,
+            "timeout hasn't elapsed yet"
+        );
+
+        zchd.zchd_tick(&cfg);
+
+        assert!(
+            zchd.zchd_sorted_inputs.zch_inputs.zch_keys.is_empty(),
+            "buffered input must be flushed once the chord times out"
+        );
+        assert!(zchd.zchd_prioritized_chords.is_none());
+        assert_eq!(zchd.zchd_num_backspaces, 0);
     }
 }